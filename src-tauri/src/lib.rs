@@ -1,4 +1,218 @@
 use tauri::{Manager, Emitter};
+use tauri_plugin_store::StoreExt;
+
+/// 设置文件名（通过 store 插件持久化用户偏好）。
+const SETTINGS_STORE: &str = "settings.json";
+
+/// 读取一个布尔设置项，读取失败或未设置时返回 `default`。
+fn bool_setting<R: tauri::Runtime>(app: &tauri::AppHandle<R>, key: &str, default: bool) -> bool {
+  app
+    .store(SETTINGS_STORE)
+    .ok()
+    .and_then(|store| store.get(key))
+    .and_then(|value| value.as_bool())
+    .unwrap_or(default)
+}
+
+/// 将 menubar 开关映射到对应的 macOS 激活策略：
+/// `true` 为 Accessory（菜单栏模式，无 Dock 图标、不进入 Cmd-Tab），
+/// `false` 为 Regular（常规应用，有 Dock 图标）。
+#[cfg(target_os = "macos")]
+fn activation_policy(menubar: bool) -> tauri::ActivationPolicy {
+  if menubar {
+    tauri::ActivationPolicy::Accessory
+  } else {
+    tauri::ActivationPolicy::Regular
+  }
+}
+
+/// 在运行时切换菜单栏模式：更新 Dock 图标可见性并持久化设置。
+#[tauri::command]
+fn set_menubar_mode(app: tauri::AppHandle, menubar: bool) -> Result<(), String> {
+  #[cfg(target_os = "macos")]
+  app
+    .set_activation_policy(activation_policy(menubar))
+    .map_err(|e| e.to_string())?;
+
+  let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+  store.set("menubarMode", menubar);
+  Ok(())
+}
+
+/// 快速捕获窗口的标签与默认全局快捷键。
+const QUICK_CAPTURE_LABEL: &str = "quick-capture";
+const DEFAULT_QUICK_CAPTURE_SHORTCUT: &str = "CmdOrCtrl+Shift+Space";
+
+/// 显示（或在不存在时创建）无边框、置顶的快速捕获窗口，
+/// 把它吸附到托盘附近并通知前端进入快速录入状态。
+fn show_quick_capture<R: tauri::Runtime>(app: &tauri::AppHandle<R>) {
+  let window = match app.get_webview_window(QUICK_CAPTURE_LABEL) {
+    Some(window) => window,
+    None => {
+      match tauri::WebviewWindowBuilder::new(
+        app,
+        QUICK_CAPTURE_LABEL,
+        tauri::WebviewUrl::App("index.html".into()),
+      )
+      .title("快速捕获")
+      .inner_size(520.0, 120.0)
+      .decorations(false)
+      .always_on_top(true)
+      .resizable(false)
+      .skip_taskbar(true)
+      .visible(false)
+      .build()
+      {
+        Ok(window) => window,
+        Err(_) => return,
+      }
+    }
+  };
+
+  use tauri_plugin_positioner::{Position, WindowExt};
+  let _ = window.move_window(Position::TrayCenter);
+  let _ = window.show();
+  let _ = window.set_focus();
+  let _ = window.emit("quick-capture", ());
+}
+
+/// 注册快速捕获全局快捷键；快捷键已被注册或注册失败时返回错误。
+fn register_quick_capture<R: tauri::Runtime>(app: &tauri::AppHandle<R>, accelerator: &str) -> tauri::Result<()> {
+  use tauri_plugin_global_shortcut::GlobalShortcutExt;
+  let handle = app.clone();
+  app.global_shortcut().on_shortcut(accelerator, move |_app, _shortcut, event| {
+    if event.state() == tauri_plugin_global_shortcut::ShortcutState::Pressed {
+      show_quick_capture(&handle);
+    }
+  })?;
+  Ok(())
+}
+
+/// 运行时重绑定快速捕获快捷键：注销旧组合、注册新组合并持久化。
+#[tauri::command]
+fn rebind_quick_capture(app: tauri::AppHandle, accelerator: String) -> Result<(), String> {
+  use tauri_plugin_global_shortcut::GlobalShortcutExt;
+  let _ = app.global_shortcut().unregister_all();
+  register_quick_capture(&app, &accelerator).map_err(|e| e.to_string())?;
+  let store = app.store(SETTINGS_STORE).map_err(|e| e.to_string())?;
+  store.set("quickCaptureShortcut", accelerator);
+  Ok(())
+}
+
+/// 托盘图标 id，用于运行时通过 `tray_by_id` 刷新菜单。
+const TRAY_ID: &str = "main-tray";
+
+/// 从 store 读取最近笔记列表（形如 `[{ "id": "...", "title": "..." }]`），
+/// 供托盘“最近笔记”子菜单使用；读取失败时返回空列表。
+fn recent_notes<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> Vec<(String, String)> {
+  app
+    .store(SETTINGS_STORE)
+    .ok()
+    .and_then(|store| store.get("recentNotes"))
+    .and_then(|value| value.as_array().cloned())
+    .unwrap_or_default()
+    .iter()
+    .filter_map(|note| {
+      let id = note.get("id")?.as_str()?.to_string();
+      let title = note.get("title")?.as_str()?.to_string();
+      Some((id, title))
+    })
+    .collect()
+}
+
+/// 构建托盘菜单：显示/设置、快捷动作（新建笔记、搜索）、
+/// 最近笔记子菜单，以及检查更新与退出，组间以分隔符分隔。
+fn build_tray_menu<R: tauri::Runtime>(app: &tauri::AppHandle<R>) -> tauri::Result<tauri::menu::Menu<R>> {
+  use tauri::menu::{MenuItem, PredefinedMenuItem, Submenu};
+
+  let recent = Submenu::with_id(app, "recent", "最近笔记", true)?;
+  let notes = recent_notes(app);
+  if notes.is_empty() {
+    recent.append(&MenuItem::with_id(app, "recent:empty", "暂无最近笔记", false, None::<&str>)?)?;
+  } else {
+    for (id, title) in notes.iter().take(10) {
+      recent.append(&MenuItem::with_id(app, format!("note:{id}"), title, true, None::<&str>)?)?;
+    }
+  }
+
+  tauri::menu::Menu::with_items(app, &[
+    &MenuItem::with_id(app, "show", "显示主界面", true, None::<&str>)?,
+    &MenuItem::with_id(app, "settings", "设置", true, None::<&str>)?,
+    &PredefinedMenuItem::separator(app)?,
+    &MenuItem::with_id(app, "new-note", "新建笔记", true, None::<&str>)?,
+    &MenuItem::with_id(app, "search", "搜索", true, None::<&str>)?,
+    &recent,
+    &PredefinedMenuItem::separator(app)?,
+    &MenuItem::with_id(app, "check-update", "检查更新", true, None::<&str>)?,
+    &MenuItem::with_id(app, "quit", "退出", true, None::<&str>)?,
+  ])
+}
+
+/// 笔记变更后重建托盘菜单，使“最近笔记”子菜单保持最新。
+#[tauri::command]
+fn refresh_tray_menu(app: tauri::AppHandle) -> Result<(), String> {
+  let menu = build_tray_menu(&app).map_err(|e| e.to_string())?;
+  if let Some(tray) = app.tray_by_id(TRAY_ID) {
+    tray.set_menu(Some(menu)).map_err(|e| e.to_string())?;
+  }
+  Ok(())
+}
+
+/// 检查并（在用户确认后）安装更新。
+///
+/// 发现更新时通过对话框询问是否更新。`silent` 为 `true` 时（启动时的自动检查）
+/// 在无更新或检查失败时保持安静；手动检查在这两种情况下也会通过通知反馈结果。
+async fn check_for_update(app: tauri::AppHandle, silent: bool) {
+  use tauri_plugin_notification::NotificationExt;
+  use tauri_plugin_updater::UpdaterExt;
+
+  let notify = |title: &str, body: &str| {
+    let _ = app.notification().builder().title(title).body(body).show();
+  };
+
+  let updater = match app.updater() {
+    Ok(updater) => updater,
+    Err(e) => {
+      if !silent {
+        notify("检查更新失败", &e.to_string());
+      }
+      return;
+    }
+  };
+
+  match updater.check().await {
+    Ok(Some(update)) => {
+      use tauri_plugin_dialog::{DialogExt, MessageDialogButtons};
+      let confirmed = app
+        .dialog()
+        .message(format!("发现新版本 {}，是否现在更新？", update.version))
+        .title("NXmind 更新")
+        .buttons(MessageDialogButtons::OkCancel)
+        .blocking_show();
+      if !confirmed {
+        return;
+      }
+      notify("正在更新", &format!("正在下载版本 {}…", update.version));
+      match update.download_and_install(|_, _| {}, || {}).await {
+        Ok(_) => {
+          notify("更新完成", "即将重启以应用新版本。");
+          app.restart();
+        }
+        Err(e) => notify("更新失败", &e.to_string()),
+      }
+    }
+    Ok(None) => {
+      if !silent {
+        notify("检查更新", "当前已是最新版本。");
+      }
+    }
+    Err(e) => {
+      if !silent {
+        notify("检查更新失败", &e.to_string());
+      }
+    }
+  }
+}
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
@@ -8,13 +222,41 @@ pub fn run() {
     .plugin(tauri_plugin_dialog::init())
     .plugin(tauri_plugin_fs::init())
     .plugin(tauri_plugin_notification::init())
+    .plugin(tauri_plugin_store::Builder::default().build())
+    .plugin(tauri_plugin_positioner::init())
+    .plugin(tauri_plugin_updater::Builder::new().build())
     .plugin(tauri_plugin_autostart::init(tauri_plugin_autostart::MacosLauncher::LaunchAgent, Some(vec![])))
     .plugin(tauri_plugin_window_state::Builder::default().build())
     .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
       let _ = app.get_webview_window("main").expect("no main window").show();
       let _ = app.get_webview_window("main").expect("no main window").set_focus();
     }))
+    .on_window_event(|window, event| {
+      // 关闭窗口时默认隐藏到托盘而非退出，让应用常驻；
+      // 用户可在设置中关闭 closeToTray 以恢复“关闭即退出”。
+      if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+        if window.label() == "main" && bool_setting(window.app_handle(), "closeToTray", true) {
+          api.prevent_close();
+          let _ = window.hide();
+        }
+      }
+      // 快速捕获窗口失焦即隐藏，保持“弹出即走”的轻量体验。
+      if let tauri::WindowEvent::Focused(false) = event {
+        if window.label() == QUICK_CAPTURE_LABEL {
+          let _ = window.hide();
+        }
+      }
+    })
+    .invoke_handler(tauri::generate_handler![set_menubar_mode, rebind_quick_capture, refresh_tray_menu])
     .setup(|app| {
+      // macOS：根据 menubarMode 设置决定是否隐藏 Dock 图标，
+      // 让 NXmind 可作为纯菜单栏后台工具运行。
+      #[cfg(target_os = "macos")]
+      {
+        let menubar = bool_setting(app.handle(), "menubarMode", false);
+        let _ = app.set_activation_policy(activation_policy(menubar));
+      }
+
       if cfg!(debug_assertions) {
         app.handle().plugin(
           tauri_plugin_log::Builder::default()
@@ -23,14 +265,26 @@ pub fn run() {
         )?;
       }
       
+      // 注册快速捕获全局快捷键（可在设置中重绑定）。
+      {
+        let accelerator = app
+          .store(SETTINGS_STORE)
+          .ok()
+          .and_then(|store| store.get("quickCaptureShortcut"))
+          .and_then(|value| value.as_str().map(str::to_owned))
+          .unwrap_or_else(|| DEFAULT_QUICK_CAPTURE_SHORTCUT.to_string());
+        let _ = register_quick_capture(app.handle(), &accelerator);
+      }
+
+      // 启动时静默检查更新（可通过 autoCheckUpdate 设置关闭）。
+      if bool_setting(app.handle(), "autoCheckUpdate", true) {
+        tauri::async_runtime::spawn(check_for_update(app.handle().clone(), true));
+      }
+
       // Setup Tray Icon logic
-      let _ = tauri::tray::TrayIconBuilder::new()
+      let _ = tauri::tray::TrayIconBuilder::with_id(TRAY_ID)
         .icon(app.default_window_icon().unwrap().clone())
-        .menu(&tauri::menu::Menu::with_items(app.handle(), &[
-            &tauri::menu::MenuItem::with_id(app.handle(), "show", "显示主界面", true, None::<&str>)?,
-            &tauri::menu::MenuItem::with_id(app.handle(), "settings", "设置", true, None::<&str>)?,
-            &tauri::menu::MenuItem::with_id(app.handle(), "quit", "退出", true, None::<&str>)?,
-        ])?)
+        .menu(&build_tray_menu(app.handle())?)
         .on_menu_event(|app, event| {
             match event.id.as_ref() {
                 "show" => {
@@ -45,15 +299,45 @@ pub fn run() {
                    win.show().unwrap();
                    win.emit("open-settings", ()).unwrap();
                 }
-                _ => {}
+                "new-note" => show_quick_capture(app),
+                "check-update" => {
+                    let handle = app.clone();
+                    tauri::async_runtime::spawn(check_for_update(handle, false));
+                }
+                "search" => {
+                   let win = app.get_webview_window("main").unwrap();
+                   win.show().unwrap();
+                   win.set_focus().unwrap();
+                   win.emit("focus-search", ()).unwrap();
+                }
+                other => {
+                    // 最近笔记项的 id 形如 "note:<id>"，点击后跳转到对应笔记。
+                    if let Some(id) = other.strip_prefix("note:") {
+                        if let Some(win) = app.get_webview_window("main") {
+                            let _ = win.show();
+                            let _ = win.set_focus();
+                            let _ = win.emit("open-note", id.to_string());
+                        }
+                    }
+                }
             }
         })
         .on_tray_icon_event(|tray, event| {
+            let app = tray.app_handle();
+            // 把托盘事件转发给 positioner，使其记录托盘图标位置，
+            // 以便把窗口吸附到图标下方（菜单栏面板模式）。
+            tauri_plugin_positioner::on_tray_event(app, &event);
             if let tauri::tray::TrayIconEvent::Click { button: tauri::tray::MouseButton::Left, .. } = event {
-               let app = tray.app_handle();
                if let Some(win) = app.get_webview_window("main") {
-                   let _ = win.show();
-                   let _ = win.set_focus();
+                   // 左键点击在托盘图标下方切换窗口的显隐，像下拉面板一样。
+                   if win.is_visible().unwrap_or(false) {
+                       let _ = win.hide();
+                   } else {
+                       use tauri_plugin_positioner::{Position, WindowExt};
+                       let _ = win.move_window(Position::TrayBottomCenter);
+                       let _ = win.show();
+                       let _ = win.set_focus();
+                   }
                }
             }
         })